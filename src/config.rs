@@ -1,10 +1,48 @@
 use crate::Figlet;
+use serde::Deserialize;
+use std::fs;
 use std::io;
+use std::path::Path;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RgbColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl From<RgbColor> for crossterm::Color {
+    fn from(color: RgbColor) -> Self {
+        crossterm::Color::Rgb {
+            r: color.r,
+            g: color.g,
+            b: color.b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct StatusStyle {
+    pub symbol: char,
+    pub color: RgbColor,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub types: Vec<String>,
     pub figlet_file: Option<String>,
+    pub header: String,
+    pub status_untracked: StatusStyle,
+    pub status_modified: StatusStyle,
+    pub status_renamed: StatusStyle,
+    pub status_deleted: StatusStyle,
+    pub status_conflicted: StatusStyle,
+    /// `None` keeps the terminal's own named color (the out-of-the-box
+    /// look); set this in `glint.toml`/`.glintrc` to pin an explicit RGB.
+    pub focused_color: Option<RgbColor>,
+    pub default_color: Option<RgbColor>,
 }
 
 impl Config {
@@ -14,6 +52,23 @@ impl Config {
             None => Ok(Figlet::default()),
         }
     }
+
+    /// Looks for `glint.toml`, then `.glintrc`, at the repo root. Falls back
+    /// to `Config::default()` if neither exists or fails to parse.
+    pub fn discover(repo_root: &Path) -> Self {
+        for file_name in &["glint.toml", ".glintrc"] {
+            let contents = match fs::read_to_string(repo_root.join(file_name)) {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+
+            if let Ok(config) = toml::from_str(&contents) {
+                return config;
+            }
+        }
+
+        Config::default()
+    }
 }
 
 impl Default for Config {
@@ -36,6 +91,44 @@ impl Default for Config {
             .map(String::from)
             .collect(),
             figlet_file: None,
+            header: "<glint>".to_owned(),
+            status_untracked: StatusStyle {
+                symbol: '+',
+                color: RgbColor { r: 96, g: 218, b: 177 },
+            },
+            status_modified: StatusStyle {
+                symbol: '•',
+                color: RgbColor { r: 96, g: 112, b: 218 },
+            },
+            status_renamed: StatusStyle {
+                symbol: '»',
+                color: RgbColor { r: 96, g: 112, b: 218 },
+            },
+            status_deleted: StatusStyle {
+                symbol: '-',
+                color: RgbColor { r: 218, g: 96, b: 118 },
+            },
+            status_conflicted: StatusStyle {
+                symbol: '!',
+                color: RgbColor { r: 218, g: 165, b: 32 },
+            },
+            focused_color: None,
+            default_color: None,
         }
     }
 }
+
+impl Default for StatusStyle {
+    fn default() -> Self {
+        StatusStyle {
+            symbol: ' ',
+            color: RgbColor { r: 255, g: 255, b: 255 },
+        }
+    }
+}
+
+impl Default for RgbColor {
+    fn default() -> Self {
+        RgbColor { r: 255, g: 255, b: 255 }
+    }
+}
@@ -2,9 +2,12 @@ use std::env::current_dir;
 use std::ffi::OsStr;
 use std::fmt;
 use std::io::{self, BufRead, BufReader};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
 
+#[cfg(feature = "git2-backend")]
+use git2::{Repository, Status, StatusOptions};
+
 mod parse_log;
 
 pub use parse_log::LogItem;
@@ -15,6 +18,20 @@ pub struct Git {
     repo_root: PathBuf,
 }
 
+#[derive(Debug, Clone)]
+pub struct BranchStatus {
+    pub branch: String,
+    pub ahead: usize,
+    pub behind: usize,
+    pub stashed: bool,
+}
+
+impl BranchStatus {
+    pub fn diverged(&self) -> bool {
+        self.ahead > 0 && self.behind > 0
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GitStatus(pub Vec<GitStatusItem>);
 
@@ -32,6 +49,7 @@ pub enum GitStatusType {
     Renamed,
     Untracked,
     Deleted,
+    Conflicted,
     None,
 }
 
@@ -42,6 +60,7 @@ pub enum GitError {
 }
 
 impl Git {
+    #[cfg(not(feature = "git2-backend"))]
     pub fn from_cwd() -> Result<Self, GitError> {
         let cwd = current_dir().map_err(GitError::Io)?;
 
@@ -61,6 +80,20 @@ impl Git {
         }
     }
 
+    /// Discovers the repo root via libgit2 rather than walking `.git` dirs by hand.
+    #[cfg(feature = "git2-backend")]
+    pub fn from_cwd() -> Result<Self, GitError> {
+        let cwd = current_dir().map_err(GitError::Io)?;
+
+        let repo = Repository::discover(&cwd).map_err(|_| GitError::NotGitRepo)?;
+        let repo_root = repo
+            .workdir()
+            .ok_or(GitError::NotGitRepo)?
+            .to_path_buf();
+
+        Ok(Git { cwd, repo_root })
+    }
+
     pub fn commit<I>(&self, message: &str, other_args: impl IntoIterator<Item = I>) -> io::Result<ExitStatus>
     where
         I: AsRef<OsStr>,
@@ -95,6 +128,7 @@ impl Git {
     }
 
     /// Stages files using `git add`. Run from the repo root.gs
+    #[cfg(not(feature = "git2-backend"))]
     pub fn add<I>(&self, files: impl IntoIterator<Item = I>) -> io::Result<()>
     where
         I: AsRef<OsStr>,
@@ -107,6 +141,24 @@ impl Git {
         Ok(())
     }
 
+    /// Stages files by writing them directly into the repo index, in-process.
+    #[cfg(feature = "git2-backend")]
+    pub fn add<I>(&self, files: impl IntoIterator<Item = I>) -> io::Result<()>
+    where
+        I: AsRef<OsStr>,
+    {
+        let repo = Repository::open(&self.repo_root).map_err(git2_err)?;
+        let mut index = repo.index().map_err(git2_err)?;
+
+        for file in files {
+            index.add_path(Path::new(file.as_ref())).map_err(git2_err)?;
+        }
+
+        index.write().map_err(git2_err)
+    }
+
+    /// Always shells out to `git diff` piped through `less`, since the pager
+    /// needs a real process to take over the terminal.
     pub fn diff_less<I>(&self, files: impl IntoIterator<Item = I>) -> io::Result<()>
     where
         I: AsRef<OsStr>,
@@ -129,6 +181,7 @@ impl Git {
         Ok(())
     }
 
+    #[cfg(not(feature = "git2-backend"))]
     pub fn status(&self) -> io::Result<GitStatus> {
         let command = Command::new("git")
             .current_dir(&self.cwd)
@@ -145,14 +198,23 @@ impl Git {
             .filter_map(|line| line.ok())
             .filter_map(|line| {
                 let mut chars = line.chars();
-                let staged = chars
-                    .next()
-                    .and_then(GitStatusType::from_char)
-                    .filter(|item| match item {
-                        GitStatusType::Untracked => false,
-                        _ => true,
-                    });
-                let unstaged = chars.next().and_then(GitStatusType::from_char);
+                let staged_ch = chars.next();
+                let unstaged_ch = chars.next();
+
+                let (staged, unstaged) = match (staged_ch, unstaged_ch) {
+                    (Some(x), Some(y)) if GitStatusType::is_conflict_pair(x, y) => {
+                        (None, Some(GitStatusType::Conflicted))
+                    }
+                    _ => {
+                        let staged = staged_ch.and_then(GitStatusType::from_char).filter(|item| match item {
+                            GitStatusType::Untracked => false,
+                            _ => true,
+                        });
+                        let unstaged = unstaged_ch.and_then(GitStatusType::from_char);
+
+                        (staged, unstaged)
+                    }
+                };
 
                 chars.next();
                 let file: String = chars.collect();
@@ -171,7 +233,120 @@ impl Git {
 
         Ok(GitStatus(items))
     }
+
+    /// In-process equivalent of `git status --porcelain`, backed by libgit2.
+    #[cfg(feature = "git2-backend")]
+    pub fn status(&self) -> io::Result<GitStatus> {
+        let repo = Repository::open(&self.repo_root).map_err(git2_err)?;
+
+        let mut options = StatusOptions::new();
+        options.include_untracked(true).renames_head_to_index(true);
+
+        let statuses = repo.statuses(Some(&mut options)).map_err(git2_err)?;
+
+        let items = statuses
+            .iter()
+            .filter_map(|entry| {
+                let file_name = entry.path()?.to_owned();
+                let status = entry.status();
+
+                let (staged, unstaged) = if status.is_conflicted() {
+                    (None, Some(GitStatusType::Conflicted))
+                } else {
+                    (
+                        GitStatusType::from_index_status(status),
+                        GitStatusType::from_worktree_status(status),
+                    )
+                };
+
+                Some(GitStatusItem {
+                    file_name,
+                    staged,
+                    unstaged,
+                })
+            })
+            .collect();
+
+        Ok(GitStatus(items))
+    }
+
+    #[cfg(not(feature = "git2-backend"))]
+    pub fn branch_status(&self) -> io::Result<BranchStatus> {
+        let output = Command::new("git")
+            .current_dir(&self.cwd)
+            .args(&["rev-parse", "--abbrev-ref", "HEAD"])
+            .output()?;
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+
+        let output = Command::new("git")
+            .current_dir(&self.cwd)
+            .args(&["rev-list", "--left-right", "--count", "@{u}...HEAD"])
+            .output()?;
+
+        let (behind, ahead) = if output.status.success() {
+            let text = String::from_utf8_lossy(&output.stdout);
+            let mut counts = text.split_whitespace();
+            let behind = counts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+            let ahead = counts.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+            (behind, ahead)
+        } else {
+            // No upstream configured for this branch.
+            (0, 0)
+        };
+
+        Ok(BranchStatus {
+            branch,
+            ahead,
+            behind,
+            stashed: self.any_stashed()?,
+        })
+    }
+
+    /// Same ahead/behind computation as the porcelain path, but using
+    /// libgit2's merge-base graph walk instead of shelling out to `rev-list`.
+    #[cfg(feature = "git2-backend")]
+    pub fn branch_status(&self) -> io::Result<BranchStatus> {
+        let repo = Repository::open(&self.repo_root).map_err(git2_err)?;
+        let head = repo.head().map_err(git2_err)?;
+        let branch = head.shorthand().unwrap_or("HEAD").to_owned();
+
+        let (ahead, behind) = head
+            .target()
+            .map(|local_oid| {
+                repo.find_branch(&branch, git2::BranchType::Local)
+                    .ok()
+                    .and_then(|b| b.upstream().ok())
+                    .and_then(|upstream| upstream.get().target())
+                    .and_then(|upstream_oid| repo.graph_ahead_behind(local_oid, upstream_oid).ok())
+                    .unwrap_or((0, 0))
+            })
+            .unwrap_or((0, 0));
+
+        Ok(BranchStatus {
+            branch,
+            ahead,
+            behind,
+            stashed: self.any_stashed()?,
+        })
+    }
+
+    /// Whether a stash exists. Always shells out, since neither libgit2's
+    /// reflog-backed stash API nor `git stash list` is meaningfully faster.
+    fn any_stashed(&self) -> io::Result<bool> {
+        let output = Command::new("git")
+            .current_dir(&self.cwd)
+            .args(&["stash", "list"])
+            .output()?;
+
+        Ok(!output.stdout.is_empty())
+    }
 }
+
+#[cfg(feature = "git2-backend")]
+fn git2_err(err: git2::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
 impl GitStatus {
     pub fn iter(&self) -> impl Iterator<Item = &GitStatusItem> {
         self.0.iter()
@@ -185,6 +360,17 @@ impl GitStatus {
         self.iter().any(|item| item.unstaged.is_some())
     }
 
+    pub fn any_conflicted(&self) -> bool {
+        self.iter().any(GitStatusItem::is_conflicted)
+    }
+
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&GitStatusItem, &GitStatusItem) -> std::cmp::Ordering,
+    {
+        self.0.sort_by(compare);
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -204,6 +390,31 @@ impl GitStatusItem {
     pub fn status(&self) -> &GitStatusType {
         self.unstaged.as_ref().unwrap_or(&GitStatusType::None)
     }
+    pub fn is_staged(&self) -> bool {
+        self.staged.is_some()
+    }
+    /// The index (staged) half of a partially-staged entry, e.g. the `M` in `MM`.
+    pub fn staged_status(&self) -> &GitStatusType {
+        self.staged.as_ref().unwrap_or(&GitStatusType::None)
+    }
+    /// The worktree (unstaged) half of a partially-staged entry, e.g. the second `M` in `MM`.
+    pub fn unstaged_status(&self) -> &GitStatusType {
+        self.unstaged.as_ref().unwrap_or(&GitStatusType::None)
+    }
+    /// The most relevant single status for this entry: the worktree state if
+    /// there is one, otherwise the index state (e.g. a clean `git add`'d file
+    /// has no worktree half, but is still meaningfully `Added`).
+    pub fn effective_status(&self) -> &GitStatusType {
+        match self.unstaged_status() {
+            GitStatusType::None => self.staged_status(),
+            status => status,
+        }
+    }
+    /// Checks both halves explicitly rather than relying on which slot a
+    /// given `status()` implementation happens to stash the conflict marker in.
+    pub fn is_conflicted(&self) -> bool {
+        *self.staged_status() == GitStatusType::Conflicted || *self.unstaged_status() == GitStatusType::Conflicted
+    }
 }
 
 impl Into<String> for GitStatusItem {
@@ -229,6 +440,45 @@ impl GitStatusType {
             _ => None,
         }
     }
+
+    /// Porcelain reports an unmerged entry as one of these two-character
+    /// codes, where neither character can be interpreted on its own.
+    fn is_conflict_pair(staged: char, unstaged: char) -> bool {
+        matches!(
+            (staged, unstaged),
+            ('U', 'U') | ('D', 'D') | ('A', 'U') | ('U', 'A') | ('D', 'U') | ('U', 'D') | ('A', 'A')
+        )
+    }
+
+    #[cfg(feature = "git2-backend")]
+    fn from_index_status(status: Status) -> Option<Self> {
+        if status.is_index_new() {
+            Some(GitStatusType::Added)
+        } else if status.is_index_renamed() {
+            Some(GitStatusType::Renamed)
+        } else if status.is_index_modified() || status.is_index_typechange() {
+            Some(GitStatusType::Modified)
+        } else if status.is_index_deleted() {
+            Some(GitStatusType::Deleted)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "git2-backend")]
+    fn from_worktree_status(status: Status) -> Option<Self> {
+        if status.is_wt_new() {
+            Some(GitStatusType::Untracked)
+        } else if status.is_wt_renamed() {
+            Some(GitStatusType::Renamed)
+        } else if status.is_wt_modified() || status.is_wt_typechange() {
+            Some(GitStatusType::Modified)
+        } else if status.is_wt_deleted() {
+            Some(GitStatusType::Deleted)
+        } else {
+            None
+        }
+    }
 }
 
 impl fmt::Display for GitError {
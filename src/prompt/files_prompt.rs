@@ -3,8 +3,53 @@ use crate::git::{Git, GitStatus, GitStatusItem, GitStatusType};
 use crate::Config;
 use crate::TermBuffer;
 use crossterm::{self as ct, style, InputEvent, KeyEvent};
+use std::collections::HashMap;
 use std::iter;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortMode {
+    Path,
+    Status,
+    StagedFirst,
+}
+
+impl SortMode {
+    fn next(self) -> Self {
+        match self {
+            SortMode::Path => SortMode::Status,
+            SortMode::Status => SortMode::StagedFirst,
+            SortMode::StagedFirst => SortMode::Path,
+        }
+    }
+
+    fn compare(self, a: &GitStatusItem, b: &GitStatusItem) -> std::cmp::Ordering {
+        match self {
+            SortMode::Path => a.file_name().cmp(b.file_name()),
+            SortMode::Status => status_rank(a.effective_status())
+                .cmp(&status_rank(b.effective_status()))
+                .then_with(|| a.file_name().cmp(b.file_name())),
+            SortMode::StagedFirst => b
+                .is_staged()
+                .cmp(&a.is_staged())
+                .then_with(|| status_rank(a.effective_status()).cmp(&status_rank(b.effective_status())))
+                .then_with(|| a.file_name().cmp(b.file_name())),
+        }
+    }
+}
+
+/// Orders statuses the way `--gitsort` groups them: staged-ish changes
+/// first, then modified, then untracked, then deleted.
+fn status_rank(status: &GitStatusType) -> u8 {
+    match status {
+        GitStatusType::Added | GitStatusType::Renamed => 0,
+        GitStatusType::Modified => 1,
+        GitStatusType::Untracked => 2,
+        GitStatusType::Deleted => 3,
+        GitStatusType::Conflicted => 4,
+        GitStatusType::None => 5,
+    }
+}
+
 #[derive(Debug)]
 pub struct FilesPrompt<'a> {
     config: &'a Config,
@@ -12,6 +57,7 @@ pub struct FilesPrompt<'a> {
     focused_index: u16,
     options: GitStatus,
     git: &'a Git,
+    sort_mode: SortMode,
 }
 
 pub enum FilesPromptResult {
@@ -22,13 +68,51 @@ pub enum FilesPromptResult {
 
 impl<'a> FilesPrompt<'a> {
     pub fn new(config: &'a Config, git: &'a Git, options: GitStatus) -> Self {
-        FilesPrompt {
+        let mut prompt = FilesPrompt {
             config,
             checked: (0..options.len()).map(|_| false).collect(),
             focused_index: 0,
             options,
             git,
-        }
+            sort_mode: SortMode::Path,
+        };
+        prompt.resort(SortMode::Path);
+        prompt
+    }
+
+    /// Re-sorts `options` into `mode`, reindexing `checked` and
+    /// `focused_index` so they keep tracking the same files/item.
+    fn resort(&mut self, mode: SortMode) {
+        let focused_file = if self.focused_index == 0 {
+            None
+        } else {
+            self.options
+                .iter()
+                .nth(self.focused_index as usize - 1)
+                .map(|item| item.file_name().to_owned())
+        };
+
+        let mut checked_by_file: HashMap<String, bool> = self
+            .options
+            .iter()
+            .zip(self.checked.iter())
+            .map(|(item, &checked)| (item.file_name().to_owned(), checked))
+            .collect();
+
+        self.options.sort_by(|a, b| mode.compare(a, b));
+
+        self.checked = self
+            .options
+            .iter()
+            .map(|item| checked_by_file.remove(item.file_name()).unwrap_or(false))
+            .collect();
+
+        self.focused_index = focused_file
+            .and_then(|file| self.options.iter().position(|item| item.file_name() == file))
+            .map(|i| i as u16 + 1)
+            .unwrap_or(self.focused_index);
+
+        self.sort_mode = mode;
     }
 
     pub fn run(mut self) -> FilesPromptResult {
@@ -42,6 +126,11 @@ impl<'a> FilesPrompt<'a> {
             .get_figlet()
             .expect("Ensure figlet_file points to a valid file, or remove it.");
 
+        // Computed once: nothing in this prompt's own event loop (toggling,
+        // sorting, diffing) changes the branch/ahead/behind/stash state, and
+        // recomputing it on every keystroke means re-spawning `git` per frame.
+        let branch_status = self.git.branch_status().ok();
+
         let mut first_iteration = true;
         loop {
             let event = if first_iteration {
@@ -70,6 +159,9 @@ impl<'a> FilesPrompt<'a> {
                         self.checked[index - 1] = !self.checked[index - 1];
                     }
                 }
+                Some(InputEvent::Keyboard(KeyEvent::Char('s'))) => {
+                    self.resort(self.sort_mode.next());
+                }
                 Some(InputEvent::Keyboard(KeyEvent::Char('d'))) => {
                     let index = self.focused_index as usize;
                     let files = if index == 0 {
@@ -118,7 +210,7 @@ impl<'a> FilesPrompt<'a> {
             };
 
             let mut header = figlet.create_vec();
-            figlet.write_to_buf_color("<glint>", header.as_mut_slice(), |s| {
+            figlet.write_to_buf_color(&self.config.header, header.as_mut_slice(), |s| {
                 ct::style(s).with(ct::Color::Magenta).to_string()
             });
 
@@ -126,21 +218,59 @@ impl<'a> FilesPrompt<'a> {
                 buffer.push_line(line);
             }
 
-            let prompt_pre = "Toggle files to commit (with <space>, or tap 'd' for diff):";
+            if let Some(branch_status) = &branch_status {
+                let mut line = style(&branch_status.branch).with(ct::Color::Magenta).to_string();
+
+                if branch_status.diverged() {
+                    line.push_str(&format!(" {}", style('⇕').with(ct::Color::Yellow)));
+                } else if branch_status.ahead > 0 {
+                    line.push_str(&format!(" {}{}", style('⇡').with(ct::Color::Green), branch_status.ahead));
+                } else if branch_status.behind > 0 {
+                    line.push_str(&format!(" {}{}", style('⇣').with(ct::Color::Red), branch_status.behind));
+                }
+
+                if branch_status.stashed {
+                    line.push_str(&format!(" {}", style('$').with(ct::Color::Yellow)));
+                }
+
+                buffer.push_line(format!("{}{}", line, reset_display()));
+            }
+
+            let prompt_pre = "Toggle files to commit (with <space>, 'd' for diff, 's' to sort):";
             let underscores = "-".repeat(prompt_pre.len());
             buffer.push_line("");
             buffer.push_line(prompt_pre);
             buffer.push_line(format!("{}{}", underscores, reset_display()));
 
+            if self.options.any_conflicted() {
+                let warning = "! Unresolved merge conflicts — resolve before committing.";
+                buffer.push_line(style(warning).with(self.config.status_conflicted.color.into()).to_string());
+            }
+
             let y_offset = buffer.lines() + self.focused_index;
 
-            let focused_color = ct::Color::Blue;
-            let default_color = ct::Color::White;
+            let focused_color = self.config.focused_color.map(Into::into).unwrap_or(ct::Color::Blue);
+            let default_color = self.config.default_color.map(Into::into).unwrap_or(ct::Color::White);
 
-            let status_untracked = style('+').with(ct::Color::Rgb { r: 96, g: 218, b: 177 });
-            let status_modified = style('•').with(ct::Color::Rgb { r: 96, g: 112, b: 218 });
-            let status_deleted = style('-').with(ct::Color::Rgb { r: 218, g: 96, b: 118 });
-            let status_none = style(' ').with(default_color);
+            // Two-column indicator: index (staged) state, then worktree (unstaged) state.
+            let status_indicator = |status: &GitStatusType| match status {
+                GitStatusType::Added | GitStatusType::Untracked => {
+                    style(self.config.status_untracked.symbol).with(self.config.status_untracked.color.into())
+                }
+                GitStatusType::Modified => {
+                    style(self.config.status_modified.symbol).with(self.config.status_modified.color.into())
+                }
+                GitStatusType::Renamed => {
+                    style(self.config.status_renamed.symbol).with(self.config.status_renamed.color.into())
+                }
+                GitStatusType::Deleted => {
+                    style(self.config.status_deleted.symbol).with(self.config.status_deleted.color.into())
+                }
+                GitStatusType::Conflicted => {
+                    style(self.config.status_conflicted.symbol).with(self.config.status_conflicted.color.into())
+                }
+                GitStatusType::None => style(' ').with(default_color),
+            };
 
             // Padded limit (never overflows by 1 item)
             let total = self.options.len();
@@ -161,19 +291,16 @@ impl<'a> FilesPrompt<'a> {
                 };
                 let prefix = style(if checked { '☑' } else { '□' }).with(line_color);
 
-                let file_status = match *git_status_item.status() {
-                    GitStatusType::Untracked => &status_untracked,
-                    GitStatusType::Modified => &status_modified,
-                    GitStatusType::Deleted => &status_deleted,
-                    _ => &status_none,
-                };
+                let staged_indicator = status_indicator(git_status_item.staged_status());
+                let unstaged_indicator = status_indicator(git_status_item.unstaged_status());
 
                 let file_name = style(git_status_item.file_name()).with(line_color);
 
                 let line = format!(
-                    "{} {} {}{}",
+                    "{} {}{} {}{}",
                     prefix,
-                    file_status,
+                    staged_indicator,
+                    unstaged_indicator,
                     file_name,
                     reset_display()
                 );